@@ -1,6 +1,10 @@
 /* src/admin.rs */
 
-use crate::{AppState, config, error::AppError};
+use crate::{
+    AppState,
+    config::{self, BackendKind, ProjectConfig},
+    error::AppError,
+};
 use axum::{
     Json,
     extract::{Path, State},
@@ -15,12 +19,28 @@ use std::sync::Arc;
 pub struct CreateProjectPayload {
     name: String,
     path: String,
+    #[serde(default)]
+    backend: BackendKind,
 }
 
 // Payload for updating an existing project's path.
 #[derive(Deserialize)]
 pub struct UpdateProjectPayload {
     path: String,
+    // Leave the backend untouched when omitted, rather than silently
+    // resetting it back to the Pathmap default.
+    backend: Option<BackendKind>,
+}
+
+// Builds the config entry for a project, preferring the compact `Simple`
+// form when the backend is the default so existing config files don't grow
+// a `backend` key they never asked for.
+fn project_config(path: String, backend: BackendKind) -> ProjectConfig {
+    if backend == BackendKind::Pathmap {
+        ProjectConfig::Simple(path)
+    } else {
+        ProjectConfig::Detailed { path, backend }
+    }
 }
 
 // GET /_admin/projects
@@ -48,7 +68,8 @@ pub async fn create_project(
         )));
     }
 
-    cfg.projects.insert(payload.name, payload.path);
+    cfg.projects
+        .insert(payload.name, project_config(payload.path, payload.backend));
     config::save_config(&cfg).await?;
 
     Ok(StatusCode::CREATED)
@@ -64,11 +85,13 @@ pub async fn update_project(
 
     let mut cfg = config::load_config().await?;
 
-    if !cfg.projects.contains_key(&project) {
+    let Some(existing) = cfg.projects.get(&project) else {
         return Err(AppError::ProjectNotFound(project));
-    }
+    };
+    let backend = payload.backend.unwrap_or_else(|| existing.backend());
 
-    cfg.projects.insert(project, payload.path);
+    cfg.projects
+        .insert(project, project_config(payload.path, backend));
     config::save_config(&cfg).await?;
 
     Ok(StatusCode::OK)
@@ -1,16 +1,104 @@
 /* src/api.rs */
 
-use crate::{AppState, admin, error::AppError};
+use crate::{
+    AppState, admin,
+    error::AppError,
+    health, jobs,
+    manager::ChangeEvent,
+};
 use axum::{
     Json, Router,
     extract::{Path, State},
     http::StatusCode,
-    routing::{get, put},
+    response::sse::{Event, KeepAlive, Sse},
+    routing::{get, post, put},
 };
+use fancy_log::{LogLevel, log};
+use futures::stream::{Stream, StreamExt as FuturesStreamExt};
 use pathmap::Listing;
 use serde::Serialize;
 use serde_json::Value;
-use std::sync::Arc;
+use std::{convert::Infallible, env, sync::Arc, time::Duration};
+use tokio_stream::wrappers::{BroadcastStream, errors::BroadcastStreamRecvError};
+use tower_http::compression::{
+    CompressionLayer,
+    predicate::{DefaultPredicate, Predicate, SizeAbove},
+};
+
+/// How often a keep-alive comment is sent down an idle `/watch` stream so
+/// proxies and load balancers don't time out the connection.
+const WATCH_KEEP_ALIVE_SECS: u64 = 15;
+
+/// Default minimum response size (in bytes) worth compressing. Small
+/// responses cost more in CPU than the bytes saved on the wire.
+const DEFAULT_COMPRESSION_MIN_SIZE: u16 = 1024;
+
+/// The encodings enabled when `COMPRESSION_ENCODINGS` isn't set.
+const DEFAULT_COMPRESSION_ENCODINGS: &str = "gzip,br";
+
+/// Negotiated response compression settings, read from environment
+/// variables alongside `PORT`/`LOG_LEVEL` in `main.rs`.
+///
+/// `min_size` is capped at 65535 bytes (`u16::MAX`) — there's little value
+/// in compressing above that anyway, but a `COMPRESSION_MIN_SIZE` set
+/// higher than the cap falls back to the default rather than wrapping.
+pub struct CompressionConfig {
+    min_size: u16,
+    gzip: bool,
+    br: bool,
+    deflate: bool,
+    zstd: bool,
+}
+
+impl CompressionConfig {
+    pub fn from_env() -> Self {
+        let min_size = match env::var("COMPRESSION_MIN_SIZE") {
+            Ok(raw) => raw.parse().unwrap_or_else(|_| {
+                log(
+                    LogLevel::Warn,
+                    &format!(
+                        "COMPRESSION_MIN_SIZE='{}' is not a valid size in bytes (0-{}); using the default of {} bytes.",
+                        raw,
+                        u16::MAX,
+                        DEFAULT_COMPRESSION_MIN_SIZE
+                    ),
+                );
+                DEFAULT_COMPRESSION_MIN_SIZE
+            }),
+            Err(_) => DEFAULT_COMPRESSION_MIN_SIZE,
+        };
+
+        let encodings = env::var("COMPRESSION_ENCODINGS")
+            .unwrap_or_else(|_| DEFAULT_COMPRESSION_ENCODINGS.to_string());
+        let enabled = |name: &str| {
+            encodings
+                .split(',')
+                .any(|encoding| encoding.trim().eq_ignore_ascii_case(name))
+        };
+
+        Self {
+            min_size,
+            gzip: enabled("gzip"),
+            br: enabled("br"),
+            deflate: enabled("deflate"),
+            zstd: enabled("zstd"),
+        }
+    }
+
+    // Keep `DefaultPredicate`'s built-in guards (e.g. `NotForContentType`
+    // skipping `text/event-stream`) and layer the size threshold on top,
+    // rather than replacing them — otherwise the `/watch` SSE streams added
+    // in an earlier change would get buffered and compressed.
+    fn into_layer(self) -> CompressionLayer<impl Predicate> {
+        let predicate = DefaultPredicate::new().and(SizeAbove::new(self.min_size));
+        CompressionLayer::new()
+            .gzip(self.gzip)
+            .br(self.br)
+            .deflate(self.deflate)
+            .zstd(self.zstd)
+            .compress_when(predicate)
+    }
+}
 
 // This type alias makes the handler signatures cleaner
 type AppStateExtractor = State<Arc<AppState>>;
@@ -33,7 +121,7 @@ impl From<Listing> for ListingResponse {
 }
 
 // Creates the main router for the application.
-pub fn create_router(state: Arc<AppState>) -> Router {
+pub fn create_router(state: Arc<AppState>, compression: CompressionConfig) -> Router {
     // Admin router for managing the service itself. Remains prefixed.
     let admin_router = Router::new()
         .route(
@@ -43,7 +131,11 @@ pub fn create_router(state: Arc<AppState>) -> Router {
         .route(
             "/_namespaced/projects/{project}",
             put(admin::update_project).delete(admin::delete_project),
-        );
+        )
+        .route("/_namespaced/health", get(health::health_check))
+        .route("/_namespaced/jobs/import/{project}", post(jobs::import_tree))
+        .route("/_namespaced/jobs/export/{project}", post(jobs::export_tree))
+        .route("/_namespaced/jobs/{id}", get(jobs::job_status));
 
     // Main API router with distinct top-level actions.
     let api_router = Router::new()
@@ -59,12 +151,18 @@ pub fn create_router(state: Arc<AppState>) -> Router {
                 .post(set_value)
                 .put(overwrite_value)
                 .delete(delete_value),
-        );
+        )
+        // Live change subscriptions, streamed as Server-Sent Events.
+        .route("/watch/{project}", get(watch_project))
+        .route("/watch/{project}/{path}", get(watch_path));
 
-    // Combine all routers.
+    // Combine all routers, compressing responses above the configured
+    // threshold (e.g. large `get_value`/`list_path_contents` bodies) when
+    // the client's `Accept-Encoding` allows it.
     Router::new()
         .merge(admin_router)
         .merge(api_router)
+        .layer(compression.into_layer())
         .with_state(state)
 }
 
@@ -136,3 +234,79 @@ async fn check_existence(
         Err(AppError::NotFound(format!("{}::{}", project, path)))
     }
 }
+
+/// GET /watch/{project}
+async fn watch_project(
+    State(state): AppStateExtractor,
+    Path(project): Path<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    watch(state, project, None)
+}
+
+/// GET /watch/{project}/{path}
+async fn watch_path(
+    State(state): AppStateExtractor,
+    Path((project, path)): Path<(String, String)>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    watch(state, project, Some(path))
+}
+
+// Returns whether `path` is `prefix` itself or one of its `/`-separated
+// descendants, not just any sibling that happens to share its leading
+// characters (e.g. a subscription to "a" must not see events for "a2").
+fn path_matches_prefix(path: &str, prefix: &str) -> bool {
+    path == prefix || path.starts_with(&format!("{}/", prefix))
+}
+
+// Subscribes to a project's change-event broadcaster and turns it into an
+// SSE stream, optionally filtered to events at `prefix` or one of its
+// `/`-separated descendants.
+//
+// The stream is cut off as soon as `state.shutdown_token` is cancelled, not
+// just when the client disconnects — otherwise an open `/watch` connection
+// would block `axum::serve(...).with_graceful_shutdown(...)` from ever
+// returning, and the cancellation-token shutdown path would never run.
+fn watch(
+    state: Arc<AppState>,
+    project: String,
+    prefix: Option<String>,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, AppError> {
+    let receiver = state.manager.subscribe(&project)?;
+    let shutdown = state.shutdown_token.clone();
+
+    // Qualified rather than a bare `.filter_map(...)` call: only
+    // `futures::StreamExt` is imported (for `take_until` below), and its
+    // `filter_map` takes a closure returning a future, whereas this
+    // synchronous closure needs `tokio_stream::StreamExt::filter_map`.
+    let stream = tokio_stream::StreamExt::filter_map(BroadcastStream::new(receiver), move |item| {
+        match item {
+            Ok(event) => match &prefix {
+                Some(prefix) if !path_matches_prefix(&event.path, prefix) => None,
+                _ => Some(Ok(to_sse_event(&event))),
+            },
+            Err(BroadcastStreamRecvError::Lagged(skipped)) => {
+                log(
+                    LogLevel::Warn,
+                    &format!(
+                        "Watcher for project '{}' lagged behind and missed {} change event(s).",
+                        project, skipped
+                    ),
+                );
+                None
+            }
+        }
+    });
+    let stream = FuturesStreamExt::take_until(stream, shutdown.cancelled_owned());
+
+    Ok(Sse::new(stream).keep_alive(
+        KeepAlive::new().interval(Duration::from_secs(WATCH_KEEP_ALIVE_SECS)),
+    ))
+}
+
+// Serializes a `ChangeEvent` as a JSON SSE `data:` frame.
+fn to_sse_event(event: &ChangeEvent) -> Event {
+    match serde_json::to_string(event) {
+        Ok(json) => Event::default().data(json),
+        Err(e) => Event::default().data(format!("{{\"error\":\"failed to serialize event: {}\"}}", e)),
+    }
+}
@@ -3,18 +3,59 @@
 use crate::{error::AppError, manager::PathmapManager};
 use fancy_log::{LogLevel, log};
 use notify::{RecursiveMode, Watcher, event::EventKind};
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, path::Path, sync::Arc};
 use tokio::fs;
 use tokio::sync::mpsc::{self, Sender}; // Corrected: Import mpsc and Sender correctly
+use tokio_util::sync::CancellationToken;
 
 pub const CONFIG_PATH: &str = "/opt/namespaced/pathmap.json";
 
+// Which `Storage` implementation a project is mounted on. Defaults to the
+// original on-disk Pathmap store so existing configs keep working unchanged.
+#[derive(Deserialize, Serialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendKind {
+    #[default]
+    Pathmap,
+    Memory,
+}
+
+// A project's configuration: either a bare base path (shorthand for the
+// default Pathmap backend) or an object naming the backend explicitly, e.g.
+// `{"path": "/opt/ns/x", "backend": "memory"}`.
+#[derive(Deserialize, Serialize, Debug, Clone)]
+#[serde(untagged)]
+pub enum ProjectConfig {
+    Simple(String),
+    Detailed {
+        path: String,
+        #[serde(default)]
+        backend: BackendKind,
+    },
+}
+
+impl ProjectConfig {
+    pub fn path(&self) -> &str {
+        match self {
+            ProjectConfig::Simple(path) => path,
+            ProjectConfig::Detailed { path, .. } => path,
+        }
+    }
+
+    pub fn backend(&self) -> BackendKind {
+        match self {
+            ProjectConfig::Simple(_) => BackendKind::default(),
+            ProjectConfig::Detailed { backend, .. } => *backend,
+        }
+    }
+}
+
 // Represents the structure of the JSON configuration file.
-#[derive(Deserialize, Debug, Clone)]
+#[derive(Deserialize, Serialize, Debug, Clone)]
 pub struct NamespacedConfig {
     #[serde(flatten)]
-    pub projects: HashMap<String, String>, // project_name -> base_path
+    pub projects: HashMap<String, ProjectConfig>, // project_name -> config
 }
 
 // Loads the configuration file from disk.
@@ -55,8 +96,10 @@ pub async fn load_and_apply_config(manager: Arc<PathmapManager>) -> Result<(), A
     Ok(())
 }
 
-// Watches the config file for changes and sends an event through the channel.
-pub async fn watch_config(tx: Sender<()>, manager: Arc<PathmapManager>) {
+// Watches the config file for changes and sends an event through the
+// channel. Exits as soon as `token` is cancelled instead of being dropped
+// mid-reload when the server shuts down.
+pub async fn watch_config(tx: Sender<()>, manager: Arc<PathmapManager>, token: CancellationToken) {
     let (watcher_tx, mut watcher_rx) = mpsc::channel(1);
 
     let mut watcher = match notify::recommended_watcher(move |res| {
@@ -92,7 +135,18 @@ pub async fn watch_config(tx: Sender<()>, manager: Arc<PathmapManager>) {
         &format!("Watching for changes in {}", CONFIG_PATH),
     );
 
-    while let Some(event) = watcher_rx.recv().await {
+    loop {
+        let event = tokio::select! {
+            _ = token.cancelled() => {
+                log(LogLevel::Info, "Config watcher shutting down.");
+                break;
+            }
+            event = watcher_rx.recv() => match event {
+                Some(event) => event,
+                None => break,
+            },
+        };
+
         match event.kind {
             EventKind::Modify(_) | EventKind::Create(_) => {
                 log(
@@ -0,0 +1,44 @@
+/* src/health.rs */
+
+use crate::AppState;
+use axum::{Json, extract::State, http::StatusCode};
+use serde::Serialize;
+use std::{collections::HashMap, sync::Arc};
+
+// A reserved key probed on every project's backend to confirm it is
+// reachable. It is never written; `exists` returning at all (true or false)
+// is proof enough that the store answered the request.
+const HEALTH_CHECK_PATH: &str = "__namespaced_health_check__";
+
+#[derive(Serialize)]
+pub struct ProjectHealth {
+    ready: bool,
+    base_path: String,
+}
+
+#[derive(Serialize)]
+pub struct HealthReport {
+    status: &'static str,
+    projects: HashMap<String, ProjectHealth>,
+}
+
+// GET /_namespaced/health
+pub async fn health_check(State(state): State<Arc<AppState>>) -> (StatusCode, Json<HealthReport>) {
+    let mut projects = HashMap::new();
+    let mut all_ready = true;
+
+    for (name, base_path) in state.manager.list_instances() {
+        let ready = state.manager.exists(&name, HEALTH_CHECK_PATH).await.is_ok();
+        all_ready &= ready;
+        projects.insert(name, ProjectHealth { ready, base_path });
+    }
+
+    let status = if all_ready { "ok" } else { "degraded" };
+    let code = if all_ready {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (code, Json(HealthReport { status, projects }))
+}
@@ -0,0 +1,395 @@
+/* src/jobs.rs */
+
+use axum::{
+    Json,
+    extract::{Path, State},
+    http::StatusCode,
+};
+use dashmap::DashMap;
+use fancy_log::{LogLevel, log};
+use futures::future::{BoxFuture, FutureExt};
+use serde::Serialize;
+use serde_json::{Value, json};
+use std::sync::Arc;
+use tokio::sync::mpsc::{self, Receiver, Sender};
+use uuid::Uuid;
+
+use crate::{AppState, error::AppError, manager::PathmapManager};
+
+pub type JobId = String;
+
+/// Work handed to the job worker task. Kept separate from `JobStatus` so
+/// the queue channel only carries what the worker needs to do the work.
+enum JobRequest {
+    Import {
+        id: JobId,
+        project: String,
+        tree: Value,
+    },
+    Export {
+        id: JobId,
+        project: String,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum JobState {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct JobStatus {
+    pub state: JobState,
+    pub keys_processed: u64,
+    pub error: Option<String>,
+    pub result: Option<Value>,
+}
+
+impl JobStatus {
+    fn queued() -> Self {
+        Self {
+            state: JobState::Queued,
+            keys_processed: 0,
+            error: None,
+            result: None,
+        }
+    }
+
+    fn running(keys_processed: u64) -> Self {
+        Self {
+            state: JobState::Running,
+            keys_processed,
+            error: None,
+            result: None,
+        }
+    }
+
+    fn failed(keys_processed: u64, error: AppError) -> Self {
+        Self {
+            state: JobState::Failed,
+            keys_processed,
+            error: Some(error.to_string()),
+            result: None,
+        }
+    }
+
+    fn done(keys_processed: u64, result: Option<Value>) -> Self {
+        Self {
+            state: JobState::Done,
+            keys_processed,
+            error: None,
+            result,
+        }
+    }
+}
+
+// Tracks queued/running/finished jobs and hands work to the worker task via
+// a bounded mpsc channel, so import/export run off the request path. The
+// queue holds 32 jobs; while it is full, enqueueing waits for a free slot.
+pub struct JobQueue {
+    statuses: DashMap<JobId, JobStatus>,
+    tx: Sender<JobRequest>,
+}
+
+impl JobQueue {
+    pub fn new() -> (Arc<Self>, Receiver<JobRequest>) {
+        let (tx, rx) = mpsc::channel(32);
+        (
+            Arc::new(Self {
+                statuses: DashMap::new(),
+                tx,
+            }),
+            rx,
+        )
+    }
+
+    pub fn status(&self, id: &str) -> Option<JobStatus> {
+        self.statuses.get(id).map(|entry| entry.value().clone())
+    }
+
+    fn set(&self, id: &str, status: JobStatus) {
+        self.statuses.insert(id.to_string(), status);
+    }
+
+    pub async fn enqueue_import(&self, project: String, tree: Value) -> JobId {
+        let id = Uuid::new_v4().to_string();
+        self.statuses.insert(id.clone(), JobStatus::queued());
+        let _ = self
+            .tx
+            .send(JobRequest::Import {
+                id: id.clone(),
+                project,
+                tree,
+            })
+            .await;
+        id
+    }
+
+    pub async fn enqueue_export(&self, project: String) -> JobId {
+        let id = Uuid::new_v4().to_string();
+        self.statuses.insert(id.clone(), JobStatus::queued());
+        let _ = self
+            .tx
+            .send(JobRequest::Export {
+                id: id.clone(),
+                project,
+            })
+            .await;
+        id
+    }
+}
+
+// Drains queued jobs one at a time on a dedicated task, reporting progress
+// (keys processed) back into the shared status map as it goes.
+pub async fn run_worker(
+    queue: Arc<JobQueue>,
+    mut rx: Receiver<JobRequest>,
+    manager: Arc<PathmapManager>,
+) {
+    while let Some(request) = rx.recv().await {
+        match request {
+            JobRequest::Import { id, project, tree } => {
+                run_import(&queue, &manager, &id, &project, tree).await
+            }
+            JobRequest::Export { id, project } => run_export(&queue, &manager, &id, &project).await,
+        }
+    }
+}
+
+async fn run_import(queue: &JobQueue, manager: &PathmapManager, id: &str, project: &str, tree: Value) {
+    queue.set(id, JobStatus::running(0));
+
+    if !tree.is_object() {
+        let err = AppError::AdminOperationFailed(
+            "Import body must be a JSON object mapping namespaces to values.".to_string(),
+        );
+        queue.set(id, JobStatus::failed(0, err));
+        return;
+    }
+
+    let mut leaves = Vec::new();
+    flatten_tree(String::new(), tree, &mut leaves);
+
+    let mut processed = 0u64;
+    for (path, value) in leaves {
+        // `overwrite`, not `set`: a bulk import into a non-empty project
+        // should replace pre-existing keys rather than abort the whole job
+        // on the first `set` conflict.
+        if let Err(e) = manager.overwrite(project, &path, &value).await {
+            log(
+                LogLevel::Error,
+                &format!("Import job {} failed at '{}': {}", id, path, e),
+            );
+            queue.set(id, JobStatus::failed(processed, e));
+            return;
+        }
+        processed += 1;
+        queue.set(id, JobStatus::running(processed));
+    }
+
+    queue.set(id, JobStatus::done(processed, None));
+}
+
+// Flattens a nested JSON document into `(path, leaf_value)` pairs using the
+// same `/`-separated path scheme the rest of the API uses, e.g.
+// `{"a": {"b": 1}}` becomes `[("a/b", 1)]`. An empty object is written as a
+// leaf value (rather than silently dropped) once it's nested under a
+// non-empty path; a prefix-less leaf (an empty or non-object top-level
+// body) has nowhere valid to write to and is skipped.
+fn flatten_tree(prefix: String, value: Value, out: &mut Vec<(String, Value)>) {
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            for (key, child) in map {
+                let path = if prefix.is_empty() {
+                    key
+                } else {
+                    format!("{}/{}", prefix, key)
+                };
+                flatten_tree(path, child, out);
+            }
+        }
+        leaf if !prefix.is_empty() => out.push((prefix, leaf)),
+        _ => {}
+    }
+}
+
+async fn run_export(queue: &JobQueue, manager: &PathmapManager, id: &str, project: &str) {
+    queue.set(id, JobStatus::running(0));
+
+    let namespaces = match manager.list_ns(project).await {
+        Ok(ns) => ns,
+        Err(e) => {
+            queue.set(id, JobStatus::failed(0, e));
+            return;
+        }
+    };
+
+    let mut tree = serde_json::Map::new();
+    let mut processed = 0u64;
+
+    for ns in namespaces {
+        let mut child = serde_json::Map::new();
+        if let Err(e) = walk(queue, manager, id, project, ns.clone(), &mut child, &mut processed).await {
+            queue.set(id, JobStatus::failed(processed, e));
+            return;
+        }
+        tree.insert(ns, Value::Object(child));
+    }
+
+    queue.set(id, JobStatus::done(processed, Some(Value::Object(tree))));
+}
+
+// Recursively walks `path`, writing every leaf value it finds into `out` at
+// the matching nested key and reporting progress after each one. Boxed
+// because async fns can't recurse directly.
+fn walk<'a>(
+    queue: &'a JobQueue,
+    manager: &'a PathmapManager,
+    id: &'a str,
+    project: &'a str,
+    path: String,
+    out: &'a mut serde_json::Map<String, Value>,
+    processed: &'a mut u64,
+) -> BoxFuture<'a, Result<(), AppError>> {
+    async move {
+        let listing = manager.list_path(project, &path).await?;
+
+        for value_name in listing.values {
+            let leaf_path = format!("{}/{}", path, value_name);
+            let value: Value = manager.get(project, &leaf_path).await?;
+            out.insert(value_name, value);
+            *processed += 1;
+            queue.set(id, JobStatus::running(*processed));
+        }
+
+        for group in listing.groups {
+            let mut child = serde_json::Map::new();
+            let child_path = format!("{}/{}", path, group);
+            walk(queue, manager, id, project, child_path, &mut child, processed).await?;
+            out.insert(group, Value::Object(child));
+        }
+
+        Ok(())
+    }
+    .boxed()
+}
+
+// --- Handlers ---
+
+/// POST /_namespaced/jobs/import/{project}
+pub async fn import_tree(
+    State(state): State<Arc<AppState>>,
+    Path(project): Path<String>,
+    Json(tree): Json<Value>,
+) -> (StatusCode, Json<Value>) {
+    let id = state.job_queue.enqueue_import(project, tree).await;
+    (StatusCode::ACCEPTED, Json(json!({ "job_id": id })))
+}
+
+/// POST /_namespaced/jobs/export/{project}
+pub async fn export_tree(
+    State(state): State<Arc<AppState>>,
+    Path(project): Path<String>,
+) -> (StatusCode, Json<Value>) {
+    let id = state.job_queue.enqueue_export(project).await;
+    (StatusCode::ACCEPTED, Json(json!({ "job_id": id })))
+}
+
+/// GET /_namespaced/jobs/{id}
+pub async fn job_status(
+    State(state): State<Arc<AppState>>,
+    Path(id): Path<String>,
+) -> Result<Json<JobStatus>, AppError> {
+    state
+        .job_queue
+        .status(&id)
+        .map(Json)
+        .ok_or_else(|| AppError::NotFound(format!("job '{}'", id)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{BackendKind, ProjectConfig};
+    use std::collections::HashMap;
+
+    fn sample_tree() -> Value {
+        serde_json::json!({
+            "alpha": {
+                "beta": 1,
+                "gamma": { "delta": "hello" }
+            },
+            "epsilon": { "zeta": true }
+        })
+    }
+
+    #[test]
+    fn flatten_tree_produces_slash_paths_for_every_leaf() {
+        let mut leaves = Vec::new();
+        flatten_tree(String::new(), sample_tree(), &mut leaves);
+        leaves.sort_by(|a, b| a.0.cmp(&b.0));
+
+        assert_eq!(
+            leaves,
+            vec![
+                ("alpha/beta".to_string(), serde_json::json!(1)),
+                ("alpha/gamma/delta".to_string(), serde_json::json!("hello")),
+                ("epsilon/zeta".to_string(), serde_json::json!(true)),
+            ]
+        );
+    }
+
+    #[test]
+    fn flatten_tree_keeps_nested_empty_objects_but_skips_a_prefixless_leaf() {
+        let mut leaves = Vec::new();
+        flatten_tree(String::new(), serde_json::json!({ "empty": {} }), &mut leaves);
+        assert_eq!(leaves, vec![("empty".to_string(), serde_json::json!({}))]);
+
+        let mut leaves = Vec::new();
+        flatten_tree(String::new(), serde_json::json!(42), &mut leaves);
+        assert!(leaves.is_empty());
+
+        let mut leaves = Vec::new();
+        flatten_tree(String::new(), serde_json::json!({}), &mut leaves);
+        assert!(leaves.is_empty());
+    }
+
+    #[tokio::test]
+    async fn export_walk_round_trips_what_import_flattened() {
+        let manager = PathmapManager::new();
+        let mut projects = HashMap::new();
+        projects.insert(
+            "test".to_string(),
+            ProjectConfig::Detailed {
+                path: String::new(),
+                backend: BackendKind::Memory,
+            },
+        );
+        manager.update_projects(projects).await;
+
+        let tree = sample_tree();
+        let mut leaves = Vec::new();
+        flatten_tree(String::new(), tree.clone(), &mut leaves);
+        for (path, value) in &leaves {
+            manager.overwrite("test", path, value).await.unwrap();
+        }
+
+        let (queue, _rx) = JobQueue::new();
+        let mut exported = serde_json::Map::new();
+        let mut processed = 0u64;
+        for ns in manager.list_ns("test").await.unwrap() {
+            let mut child = serde_json::Map::new();
+            walk(&queue, &manager, "job", "test", ns.clone(), &mut child, &mut processed)
+                .await
+                .unwrap();
+            exported.insert(ns, Value::Object(child));
+        }
+
+        assert_eq!(Value::Object(exported), tree);
+        assert_eq!(processed, leaves.len() as u64);
+    }
+}
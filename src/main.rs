@@ -5,21 +5,32 @@ use fancy_log::{LogLevel, log, set_log_level};
 use lazy_motd::lazy_motd;
 use std::{env, net::SocketAddr, sync::Arc};
 use tokio::sync::{Mutex, mpsc};
+use tokio::task::JoinSet;
+use tokio_util::sync::CancellationToken;
 
 mod admin;
 mod api;
 mod config;
 mod error;
+mod health;
+mod jobs;
 mod manager;
+mod storage;
 
-use api::create_router;
+use api::{CompressionConfig, create_router};
 use config::watch_config;
+use jobs::JobQueue;
 use manager::PathmapManager;
 
 // The shared state for our application, accessible by all handlers.
 pub struct AppState {
     pub manager: Arc<PathmapManager>,
     pub config_lock: Arc<Mutex<()>>, // Used to prevent race conditions on config file writes
+    pub job_queue: Arc<JobQueue>,
+    // Cancelled when the server starts shutting down, so long-lived
+    // connections (e.g. `/watch` SSE streams) can close themselves instead
+    // of blocking graceful shutdown indefinitely.
+    pub shutdown_token: CancellationToken,
 }
 
 #[tokio::main]
@@ -39,9 +50,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     lazy_motd!();
 
     // --- Application Setup ---
+    let (job_queue, job_rx) = JobQueue::new();
+    // Cancelling this token tells the config watcher/reload tasks and any
+    // open `/watch` streams to stop at their next checkpoint instead of
+    // being dropped or blocking shutdown indefinitely.
+    let shutdown_token = CancellationToken::new();
     let app_state = Arc::new(AppState {
         manager: Arc::new(PathmapManager::new()),
         config_lock: Arc::new(Mutex::new(())),
+        job_queue,
+        shutdown_token: shutdown_token.clone(),
     });
 
     let (tx, rx) = mpsc::channel(10);
@@ -54,17 +72,31 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         );
     }
 
+    let mut background_tasks = JoinSet::new();
+
     // Spawn the config watcher task
-    tokio::spawn(watch_config(tx, app_state.manager.clone()));
+    background_tasks.spawn(watch_config(
+        tx,
+        app_state.manager.clone(),
+        shutdown_token.clone(),
+    ));
 
     // Spawn the task to handle config updates
-    tokio::spawn(manager::handle_config_updates(
+    background_tasks.spawn(manager::handle_config_updates(
         rx,
         app_state.manager.clone(),
+        shutdown_token.clone(),
+    ));
+
+    // Spawn the background job worker (bulk import/export, snapshotting)
+    tokio::spawn(jobs::run_worker(
+        app_state.job_queue.clone(),
+        job_rx,
+        app_state.manager.clone(),
     ));
 
     // --- Start Web Server ---
-    let app = create_router(app_state);
+    let app = create_router(app_state, CompressionConfig::from_env());
     let port = env::var("PORT")
         .unwrap_or_else(|_| "19950".to_string())
         .parse::<u16>()?;
@@ -76,14 +108,30 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // --- Run with Graceful Shutdown ---
     axum::serve(listener, app)
-        .with_graceful_shutdown(shutdown_signal())
+        .with_graceful_shutdown(shutdown_signal(shutdown_token.clone()))
         .await?;
 
+    // `shutdown_signal` already cancelled the token as soon as the signal
+    // arrived; this is just a safety net before draining the background
+    // tasks in case shutdown was ever triggered some other way.
+    shutdown_token.cancel();
+    while let Some(result) = background_tasks.join_next().await {
+        if let Err(e) = result {
+            log(
+                LogLevel::Error,
+                &format!("Background task panicked during shutdown: {}", e),
+            );
+        }
+    }
+
     Ok(())
 }
 
-// Listens for the shutdown signal (Ctrl+C or SIGTERM).
-async fn shutdown_signal() {
+// Listens for the shutdown signal (Ctrl+C or SIGTERM), then cancels `token`
+// so long-lived connections can close themselves while `axum::serve` is
+// still waiting for in-flight requests to finish — otherwise an open
+// `/watch` stream would block graceful shutdown forever.
+async fn shutdown_signal(token: CancellationToken) {
     let ctrl_c = async {
         tokio::signal::ctrl_c()
             .await
@@ -110,4 +158,6 @@ async fn shutdown_signal() {
         LogLevel::Info,
         "Signal received, starting graceful shutdown.",
     );
+
+    token.cancel();
 }
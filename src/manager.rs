@@ -2,29 +2,73 @@
 
 use dashmap::DashMap;
 use fancy_log::{LogLevel, log};
-use pathmap::Pathmap;
+use pathmap::Listing;
 use serde::Serialize;
 use serde::de::DeserializeOwned;
+use serde_json::Value;
 use std::{collections::HashMap, sync::Arc};
+use tokio::sync::broadcast;
 use tokio::sync::mpsc::Receiver;
+use tokio_util::sync::CancellationToken;
 
+use crate::config::{BackendKind, ProjectConfig};
 use crate::error::AppError;
+use crate::storage::{MemoryBackend, PathmapBackend, Storage};
 
-// Manages all active Pathmap instances.
+// The capacity of each project's change-event broadcast channel. Slow
+// subscribers that fall this far behind will observe a `Lagged` error
+// rather than block writers.
+const CHANGE_EVENT_CAPACITY: usize = 256;
+
+/// The kind of mutation that produced a `ChangeEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeOp {
+    Set,
+    Overwrite,
+    Delete,
+}
+
+/// A single mutation published to a project's subscribers.
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ChangeEvent {
+    pub op: ChangeOp,
+    pub path: String,
+    pub value: Option<Value>,
+}
+
+// Manages all active project storage backends.
 pub struct PathmapManager {
     // Using DashMap for thread-safe concurrent access.
-    instances: DashMap<String, Arc<Pathmap>>,
+    instances: DashMap<String, Arc<dyn Storage>>,
+    // One broadcast sender per project, used to fan mutations out to `/watch` subscribers.
+    events: DashMap<String, broadcast::Sender<ChangeEvent>>,
+    // The configuration each live instance was built from, kept so a
+    // reload can tell whether a retained project's `path`/`backend`
+    // changed and needs its `Storage` rebuilt.
+    configs: DashMap<String, ProjectConfig>,
 }
 
 impl PathmapManager {
     pub fn new() -> Self {
         Self {
             instances: DashMap::new(),
+            events: DashMap::new(),
+            configs: DashMap::new(),
+        }
+    }
+
+    fn build_backend(cfg: &ProjectConfig) -> Arc<dyn Storage> {
+        match cfg.backend() {
+            BackendKind::Pathmap => Arc::new(PathmapBackend::new(cfg.path())),
+            BackendKind::Memory => Arc::new(MemoryBackend::new()),
         }
     }
 
-    // Updates the running instances based on the new configuration.
-    pub async fn update_projects(&self, new_projects: HashMap<String, String>) {
+    // Updates the running instances based on the new configuration: removes
+    // projects no longer present, rebuilds any retained project whose
+    // `path`/`backend` changed, and adds newly configured ones.
+    pub async fn update_projects(&self, new_projects: HashMap<String, ProjectConfig>) {
         let mut projects_to_add = new_projects.clone();
 
         self.instances.retain(|project_name, _| {
@@ -33,6 +77,8 @@ impl PathmapManager {
                     LogLevel::Info,
                     &format!("Removing project: {}", project_name),
                 );
+                self.events.remove(project_name);
+                self.configs.remove(project_name);
                 false
             } else {
                 projects_to_add.remove(project_name);
@@ -40,31 +86,94 @@ impl PathmapManager {
             }
         });
 
-        for (name, path) in projects_to_add {
+        let projects_to_rebuild: Vec<String> = self
+            .configs
+            .iter()
+            .filter_map(|entry| {
+                let new_cfg = new_projects.get(entry.key())?;
+                let old_cfg = entry.value();
+                let changed =
+                    old_cfg.path() != new_cfg.path() || old_cfg.backend() != new_cfg.backend();
+                changed.then(|| entry.key().clone())
+            })
+            .collect();
+
+        for name in projects_to_rebuild {
+            let cfg = &new_projects[&name];
             log(
                 LogLevel::Info,
-                &format!("Adding project '{}' with path '{}'", name, path),
+                &format!(
+                    "Reconfiguring project '{}' with path '{}' on {:?} backend",
+                    name,
+                    cfg.path(),
+                    cfg.backend()
+                ),
             );
-            let pm = Pathmap::new().with_base_path(&path);
-            self.instances.insert(name, Arc::new(pm));
+            // The broadcast sender is left untouched so existing `/watch`
+            // subscribers keep their subscription across the swap.
+            self.instances.insert(name.clone(), Self::build_backend(cfg));
+            self.configs.insert(name, cfg.clone());
+        }
+
+        for (name, cfg) in projects_to_add {
+            log(
+                LogLevel::Info,
+                &format!(
+                    "Adding project '{}' with path '{}' on {:?} backend",
+                    name,
+                    cfg.path(),
+                    cfg.backend()
+                ),
+            );
+            self.instances.insert(name.clone(), Self::build_backend(&cfg));
+            self.configs.insert(name.clone(), cfg);
+            let (tx, _rx) = broadcast::channel(CHANGE_EVENT_CAPACITY);
+            self.events.insert(name, tx);
         }
     }
 
-    fn get_instance(&self, project: &str) -> Result<Arc<Pathmap>, AppError> {
+    fn get_instance(&self, project: &str) -> Result<Arc<dyn Storage>, AppError> {
         self.instances
             .get(project)
             .map(|entry| entry.value().clone())
             .ok_or_else(|| AppError::ProjectNotFound(project.to_string()))
     }
 
+    /// Enumerates the currently loaded instances as `(project, base_path)`
+    /// pairs, independent of what the on-disk config currently says.
+    pub fn list_instances(&self) -> Vec<(String, String)> {
+        self.configs
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().path().to_string()))
+            .collect()
+    }
+
+    // Publishes a change event to a project's subscribers, if any are listening.
+    // A missing project entry or an absence of subscribers is not an error.
+    fn publish(&self, project: &str, op: ChangeOp, path: &str, value: Option<Value>) {
+        if let Some(sender) = self.events.get(project) {
+            let _ = sender.send(ChangeEvent {
+                op,
+                path: path.to_string(),
+                value,
+            });
+        }
+    }
+
+    /// Subscribes to the change-event stream for `project`.
+    pub fn subscribe(&self, project: &str) -> Result<broadcast::Receiver<ChangeEvent>, AppError> {
+        self.events
+            .get(project)
+            .map(|entry| entry.value().subscribe())
+            .ok_or_else(|| AppError::ProjectNotFound(project.to_string()))
+    }
+
     // --- API Methods ---
 
-    // Corrected: Manually map pathmap's error to our AppError string variant.
     pub async fn get<T: DeserializeOwned>(&self, project: &str, path: &str) -> Result<T, AppError> {
-        let pm = self.get_instance(project)?;
-        pm.get(path)
-            .await
-            .map_err(|e| AppError::Pathmap(e.to_string()))
+        let backend = self.get_instance(project)?;
+        let value = backend.get(path).await?;
+        Ok(serde_json::from_value(value)?)
     }
 
     pub async fn set<T: Serialize + Send + Sync>(
@@ -73,10 +182,11 @@ impl PathmapManager {
         path: &str,
         value: &T,
     ) -> Result<(), AppError> {
-        let pm = self.get_instance(project)?;
-        pm.set(path, value)
-            .await
-            .map_err(|e| AppError::Pathmap(e.to_string()))
+        let backend = self.get_instance(project)?;
+        let json = serde_json::to_value(value)?;
+        backend.set(path, &json).await?;
+        self.publish(project, ChangeOp::Set, path, Some(json));
+        Ok(())
     }
 
     pub async fn overwrite<T: Serialize + Send + Sync>(
@@ -85,32 +195,58 @@ impl PathmapManager {
         path: &str,
         value: &T,
     ) -> Result<(), AppError> {
-        let pm = self.get_instance(project)?;
-        pm.overwrite(path, value)
-            .await
-            .map_err(|e| AppError::Pathmap(e.to_string()))
+        let backend = self.get_instance(project)?;
+        let json = serde_json::to_value(value)?;
+        backend.overwrite(path, &json).await?;
+        self.publish(project, ChangeOp::Overwrite, path, Some(json));
+        Ok(())
     }
 
     pub async fn delete(&self, project: &str, path: &str) -> Result<(), AppError> {
-        let pm = self.get_instance(project)?;
-        pm.delete(path)
-            .await
-            .map_err(|e| AppError::Pathmap(e.to_string()))
+        let backend = self.get_instance(project)?;
+        backend.delete(path).await?;
+        self.publish(project, ChangeOp::Delete, path, None);
+        Ok(())
     }
 
     pub async fn exists(&self, project: &str, path: &str) -> Result<bool, AppError> {
-        let pm = self.get_instance(project)?;
-        pm.exists(path)
-            .await
-            .map_err(|e| AppError::Pathmap(e.to_string()))
+        let backend = self.get_instance(project)?;
+        backend.exists(path).await
+    }
+
+    pub async fn list_ns(&self, project: &str) -> Result<Vec<String>, AppError> {
+        let backend = self.get_instance(project)?;
+        backend.list_ns().await
+    }
+
+    pub async fn list_path(&self, project: &str, path: &str) -> Result<Listing, AppError> {
+        let backend = self.get_instance(project)?;
+        backend.list_path(path).await
     }
 }
 
-pub async fn handle_config_updates(mut rx: Receiver<()>, manager: Arc<PathmapManager>) {
-    while rx.recv().await.is_some() {
-        log(LogLevel::Info, "Received config update signal. Reloading.");
-        if let Err(e) = crate::config::load_and_apply_config(manager.clone()).await {
-            log(LogLevel::Error, &format!("Failed to reload config: {}", e));
+// Exits as soon as `token` is cancelled instead of being dropped mid-reload
+// when the server shuts down.
+pub async fn handle_config_updates(
+    mut rx: Receiver<()>,
+    manager: Arc<PathmapManager>,
+    token: CancellationToken,
+) {
+    loop {
+        tokio::select! {
+            _ = token.cancelled() => {
+                log(LogLevel::Info, "Config update handler shutting down.");
+                break;
+            }
+            signal = rx.recv() => {
+                if signal.is_none() {
+                    break;
+                }
+                log(LogLevel::Info, "Received config update signal. Reloading.");
+                if let Err(e) = crate::config::load_and_apply_config(manager.clone()).await {
+                    log(LogLevel::Error, &format!("Failed to reload config: {}", e));
+                }
+            }
         }
     }
 }
@@ -0,0 +1,167 @@
+/* src/storage.rs */
+
+use async_trait::async_trait;
+use dashmap::DashMap;
+use pathmap::{Listing, Pathmap};
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+use crate::error::AppError;
+
+// A pluggable key/value store backing a single project namespace. Swapping
+// the implementation behind this trait lets a project be mounted on a
+// different store without the HTTP layer or `PathmapManager` caring which
+// one it is.
+#[async_trait]
+pub trait Storage: Send + Sync {
+    async fn get(&self, path: &str) -> Result<Value, AppError>;
+    async fn set(&self, path: &str, value: &Value) -> Result<(), AppError>;
+    async fn overwrite(&self, path: &str, value: &Value) -> Result<(), AppError>;
+    async fn delete(&self, path: &str) -> Result<(), AppError>;
+    async fn exists(&self, path: &str) -> Result<bool, AppError>;
+    async fn list_ns(&self) -> Result<Vec<String>, AppError>;
+    async fn list_path(&self, path: &str) -> Result<Listing, AppError>;
+}
+
+// The default backend: an on-disk `pathmap::Pathmap` rooted at the
+// project's configured base path.
+pub struct PathmapBackend {
+    pm: Pathmap,
+}
+
+impl PathmapBackend {
+    pub fn new(base_path: &str) -> Self {
+        Self {
+            pm: Pathmap::new().with_base_path(base_path),
+        }
+    }
+}
+
+#[async_trait]
+impl Storage for PathmapBackend {
+    async fn get(&self, path: &str) -> Result<Value, AppError> {
+        self.pm
+            .get(path)
+            .await
+            .map_err(|e| AppError::Pathmap(e.to_string()))
+    }
+
+    async fn set(&self, path: &str, value: &Value) -> Result<(), AppError> {
+        self.pm
+            .set(path, value)
+            .await
+            .map_err(|e| AppError::Pathmap(e.to_string()))
+    }
+
+    async fn overwrite(&self, path: &str, value: &Value) -> Result<(), AppError> {
+        self.pm
+            .overwrite(path, value)
+            .await
+            .map_err(|e| AppError::Pathmap(e.to_string()))
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), AppError> {
+        self.pm
+            .delete(path)
+            .await
+            .map_err(|e| AppError::Pathmap(e.to_string()))
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool, AppError> {
+        self.pm
+            .exists(path)
+            .await
+            .map_err(|e| AppError::Pathmap(e.to_string()))
+    }
+
+    async fn list_ns(&self) -> Result<Vec<String>, AppError> {
+        self.pm
+            .list_ns()
+            .await
+            .map_err(|e| AppError::Pathmap(e.to_string()))
+    }
+
+    async fn list_path(&self, path: &str) -> Result<Listing, AppError> {
+        self.pm
+            .list_path(path)
+            .await
+            .map_err(|e| AppError::Pathmap(e.to_string()))
+    }
+}
+
+// A process-local, non-durable backend. Useful for scratch projects or
+// tests that don't need data to survive a restart.
+#[derive(Default)]
+pub struct MemoryBackend {
+    data: DashMap<String, Value>,
+}
+
+impl MemoryBackend {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl Storage for MemoryBackend {
+    async fn get(&self, path: &str) -> Result<Value, AppError> {
+        self.data
+            .get(path)
+            .map(|entry| entry.value().clone())
+            .ok_or_else(|| AppError::NotFound(path.to_string()))
+    }
+
+    async fn set(&self, path: &str, value: &Value) -> Result<(), AppError> {
+        if self.data.contains_key(path) {
+            return Err(AppError::Pathmap("UNIQUE constraint failed".to_string()));
+        }
+        self.data.insert(path.to_string(), value.clone());
+        Ok(())
+    }
+
+    async fn overwrite(&self, path: &str, value: &Value) -> Result<(), AppError> {
+        self.data.insert(path.to_string(), value.clone());
+        Ok(())
+    }
+
+    async fn delete(&self, path: &str) -> Result<(), AppError> {
+        self.data
+            .remove(path)
+            .map(|_| ())
+            .ok_or_else(|| AppError::NotFound(path.to_string()))
+    }
+
+    async fn exists(&self, path: &str) -> Result<bool, AppError> {
+        Ok(self.data.contains_key(path))
+    }
+
+    async fn list_ns(&self) -> Result<Vec<String>, AppError> {
+        let mut namespaces = BTreeSet::new();
+        for entry in self.data.iter() {
+            let ns = entry.key().split_once('/').map_or(entry.key().as_str(), |(ns, _)| ns);
+            namespaces.insert(ns.to_string());
+        }
+        Ok(namespaces.into_iter().collect())
+    }
+
+    async fn list_path(&self, path: &str) -> Result<Listing, AppError> {
+        let prefix = format!("{}/", path);
+        let mut groups = BTreeSet::new();
+        let mut values = Vec::new();
+        for entry in self.data.iter() {
+            let Some(rest) = entry.key().strip_prefix(&prefix) else {
+                continue;
+            };
+            match rest.split_once('/') {
+                Some((group, _)) => {
+                    groups.insert(group.to_string());
+                }
+                None => values.push(rest.to_string()),
+            }
+        }
+        Ok(Listing {
+            groups: groups.into_iter().collect(),
+            values,
+        })
+    }
+}